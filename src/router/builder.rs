@@ -1,15 +1,17 @@
 #![allow(warnings)]
 
+use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use hyper::Method;
+use regex::Regex;
 
 use handler::NewHandler;
 use router::Router;
 use router::tree::TreeBuilder;
 use router::response::finalizer::ResponseFinalizerBuilder;
 use router::route::{Delegation, Extractors, RouteImpl};
-use router::route::matcher::{RouteMatcher, MethodOnlyRouteMatcher};
+use router::route::matcher::{RouteMatcher, MethodOnlyRouteMatcher, AndRouteMatcher, AnyRouteMatcher};
 use router::route::dispatch::{PipelineHandleChain, PipelineSet, DispatcherImpl};
 use router::request::path::{PathExtractor, NoopPathExtractor};
 use router::request::query_string::{QueryStringExtractor, NoopQueryStringExtractor};
@@ -33,28 +35,35 @@ where
     F: FnOnce(&mut RouterBuilder<C, P>),
 {
     let mut tree_builder = TreeBuilder::new();
+    let mut response_finalizer_builder = ResponseFinalizerBuilder::new();
+    let mut constraints = ConstraintSet::new();
 
-    let response_finalizer = {
+    {
         let mut builder = RouterBuilder {
             node_builder: tree_builder.borrow_root_mut(),
             pipeline_chain,
             pipelines,
-            response_finalizer_builder: ResponseFinalizerBuilder::new(),
+            response_finalizer_builder: &mut response_finalizer_builder,
+            constraints: &mut constraints,
         };
 
         f(&mut builder);
+    }
 
-        builder.response_finalizer_builder.finalize()
-    };
-
-    Router::new(tree_builder.finalize(), response_finalizer)
+    Router::new(tree_builder.finalize(), response_finalizer_builder.finalize())
 }
 
+/// Named dynamic-segment constraints registered via `RouterBuilder::constraint`, so a route
+/// can reference `:id:uint` instead of repeating an inline pattern like `:id:[0-9]+`
+/// everywhere it's needed.
+pub type ConstraintSet = HashMap<String, Regex>;
+
 pub struct RouterBuilder<'a, C, P> {
     node_builder: &'a mut NodeBuilder,
     pipeline_chain: C,
     pipelines: PipelineSet<P>,
-    response_finalizer_builder: ResponseFinalizerBuilder,
+    response_finalizer_builder: &'a mut ResponseFinalizerBuilder,
+    constraints: &'a mut ConstraintSet,
 }
 
 type DefaultRouterBuilderTo<'a, C, P> = RouterBuilderTo<
@@ -86,6 +95,84 @@ where
         self.request(vec![Method::Post], path)
     }
 
+    pub fn put<'b>(&'b mut self, path: &str) -> DefaultRouterBuilderTo<'b, C, P>
+    where
+        C: PipelineHandleChain<P> + Send + Sync + 'static,
+        P: Send + Sync + 'static,
+    {
+        self.request(vec![Method::Put], path)
+    }
+
+    pub fn patch<'b>(&'b mut self, path: &str) -> DefaultRouterBuilderTo<'b, C, P>
+    where
+        C: PipelineHandleChain<P> + Send + Sync + 'static,
+        P: Send + Sync + 'static,
+    {
+        self.request(vec![Method::Patch], path)
+    }
+
+    pub fn delete<'b>(&'b mut self, path: &str) -> DefaultRouterBuilderTo<'b, C, P>
+    where
+        C: PipelineHandleChain<P> + Send + Sync + 'static,
+        P: Send + Sync + 'static,
+    {
+        self.request(vec![Method::Delete], path)
+    }
+
+    pub fn options<'b>(&'b mut self, path: &str) -> DefaultRouterBuilderTo<'b, C, P>
+    where
+        C: PipelineHandleChain<P> + Send + Sync + 'static,
+        P: Send + Sync + 'static,
+    {
+        self.request(vec![Method::Options], path)
+    }
+
+    pub fn head<'b>(&'b mut self, path: &str) -> DefaultRouterBuilderTo<'b, C, P>
+    where
+        C: PipelineHandleChain<P> + Send + Sync + 'static,
+        P: Send + Sync + 'static,
+    {
+        self.request(vec![Method::Head], path)
+    }
+
+    /// Matches every method in `all_methods()` at `path`. Unlike `delegate`, this still
+    /// uses a `MethodOnlyRouteMatcher`, so a request using a method outside that fixed list
+    /// won't match here either.
+    pub fn any<'b>(&'b mut self, path: &str) -> DefaultRouterBuilderTo<'b, C, P>
+    where
+        C: PipelineHandleChain<P> + Send + Sync + 'static,
+        P: Send + Sync + 'static,
+    {
+        self.request(all_methods(), path)
+    }
+
+    /// Resolves `path` to its tree node once and returns a `RouteBuilder` over it, so
+    /// several method/handler pairs can be registered at the same node (e.g. GET→list,
+    /// POST→create, DELETE→remove on one `/widget`) without re-walking the tree for each.
+    pub fn route<'b>(&'b mut self, path: &str) -> RouteBuilder<'b, C, P>
+    where
+        C: PipelineHandleChain<P> + Send + Sync + 'static,
+        P: Send + Sync + 'static,
+    {
+        let path = if path.starts_with("/") {
+            &path[1..]
+        } else {
+            path
+        };
+
+        let node_builder = if path.is_empty() {
+            &mut self.node_builder
+        } else {
+            build_subtree(self.node_builder, path.split("/"), &*self.constraints)
+        };
+
+        RouteBuilder {
+            node_builder,
+            pipeline_chain: self.pipeline_chain,
+            pipelines: self.pipelines.clone(),
+        }
+    }
+
     pub fn request<'b>(
         &'b mut self,
         methods: Vec<Method>,
@@ -104,7 +191,7 @@ where
         let node_builder = if path.is_empty() {
             &mut self.node_builder
         } else {
-            build_subtree(self.node_builder, path.split("/"))
+            build_subtree(self.node_builder, path.split("/"), &*self.constraints)
         };
 
         let matcher = MethodOnlyRouteMatcher::new(methods);
@@ -118,6 +205,100 @@ where
             phantom: PhantomData,
         }
     }
+
+    /// Mounts the path below `path` to a separately built `Router`, via
+    /// `RouterBuilderTo::to_router`. The request method is not considered here at all — an
+    /// `AnyRouteMatcher` is used rather than `MethodOnlyRouteMatcher`, so methods outside
+    /// `any`'s fixed list (WebDAV verbs, `CONNECT`, a custom `Method::Extension`) still
+    /// reach the sub-router, which is responsible for its own method dispatch once control
+    /// is handed off.
+    pub fn delegate<'b>(&'b mut self, path: &str) -> RouterBuilderTo<
+        'b,
+        AnyRouteMatcher,
+        C,
+        P,
+        NoopPathExtractor,
+        NoopQueryStringExtractor,
+    >
+    where
+        C: PipelineHandleChain<P> + Send + Sync + 'static,
+        P: Send + Sync + 'static,
+    {
+        let path = if path.starts_with("/") {
+            &path[1..]
+        } else {
+            path
+        };
+
+        let node_builder = if path.is_empty() {
+            &mut self.node_builder
+        } else {
+            build_subtree(self.node_builder, path.split("/"), &*self.constraints)
+        };
+
+        RouterBuilderTo {
+            matcher: AnyRouteMatcher::new(),
+            node_builder,
+            pipeline_chain: self.pipeline_chain,
+            pipelines: self.pipelines.clone(),
+            delegation: Delegation::Internal,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Groups a set of routes under a shared path prefix, without repeating the prefix on
+    /// each one. `f` is invoked with a `RouterBuilder` rooted at `path`, so routes (and
+    /// further nested scopes) registered within it share this builder's `pipeline_chain`
+    /// and `pipelines`, and are folded into the same response finalizer.
+    pub fn scope<'b, F>(&'b mut self, path: &str, f: F)
+    where
+        F: FnOnce(&mut RouterBuilder<C, P>),
+    {
+        let path = if path.starts_with("/") {
+            &path[1..]
+        } else {
+            path
+        };
+
+        let node_builder = if path.is_empty() {
+            &mut self.node_builder
+        } else {
+            build_subtree(self.node_builder, path.split("/"), &*self.constraints)
+        };
+
+        let mut builder = RouterBuilder {
+            node_builder,
+            pipeline_chain: self.pipeline_chain,
+            pipelines: self.pipelines.clone(),
+            response_finalizer_builder: &mut *self.response_finalizer_builder,
+            constraints: &mut *self.constraints,
+        };
+
+        f(&mut builder)
+    }
+
+    /// Registers `name` as shorthand for `pattern`, so a dynamic segment can reference
+    /// `:id:uint` instead of repeating `:id:[0-9]+` at every call site. Looked up by
+    /// `build_subtree` before falling back to treating the token as an inline regex, so a
+    /// name and an inline pattern can't both apply to the same segment.
+    pub fn constraint(&mut self, name: &str, pattern: &str) {
+        self.constraints.insert(
+            name.to_owned(),
+            Regex::new(&format!("^(?:{})$", pattern)).unwrap(),
+        );
+    }
+
+    /// Registers a handler invoked when no route at or below this builder's scope matches
+    /// the request, letting an app serve a custom 404 page, an SPA index, or a catch-all
+    /// proxy. Registered inside `scope`, the fallback only covers that delegated sub-tree;
+    /// requests that fall through elsewhere in the tree keep using any fallback registered
+    /// further up, mirroring actix's `DefaultResource`/axum's fallback.
+    pub fn fallback<NH>(&mut self, new_handler: NH)
+    where
+        NH: NewHandler + Send + Sync + 'static,
+    {
+        self.node_builder.set_fallback(Box::new(new_handler));
+    }
 }
 
 pub struct RouterBuilderTo<'a, M, C, P, PE, QSE>
@@ -144,6 +325,62 @@ where
     PE: PathExtractor + Send + Sync + 'static,
     QSE: QueryStringExtractor + Send + Sync + 'static,
 {
+    /// Composes an additional `RouteMatcher` onto this route, combined with AND semantics
+    /// alongside the method matcher already built by `request`/`get`/`post`. Lets a route
+    /// require, for example, a specific `Content-Type` or `Accept` header, or the presence
+    /// of a query parameter, before it matches.
+    pub fn add_matcher<M2>(
+        self,
+        matcher: M2,
+    ) -> RouterBuilderTo<'a, AndRouteMatcher<M, M2>, C, P, PE, QSE>
+    where
+        M2: RouteMatcher + Send + Sync + 'static,
+    {
+        RouterBuilderTo {
+            matcher: AndRouteMatcher::new(self.matcher, matcher),
+            node_builder: self.node_builder,
+            pipeline_chain: self.pipeline_chain,
+            pipelines: self.pipelines,
+            delegation: self.delegation,
+            phantom: self.phantom,
+        }
+    }
+
+    /// Binds a typed `PathExtractor` to this route, replacing the default
+    /// `NoopPathExtractor`. The handler will find `T` deserialized from the matched path
+    /// segments in `State`, rather than having to parse them itself. This is what makes
+    /// dynamic `:id`-style segments ergonomic to consume.
+    pub fn with_path_extractor<T>(self) -> RouterBuilderTo<'a, M, C, P, T, QSE>
+    where
+        T: PathExtractor + Send + Sync + 'static,
+    {
+        RouterBuilderTo {
+            matcher: self.matcher,
+            node_builder: self.node_builder,
+            pipeline_chain: self.pipeline_chain,
+            pipelines: self.pipelines,
+            delegation: self.delegation,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Binds a typed `QueryStringExtractor` to this route, replacing the default
+    /// `NoopQueryStringExtractor`. The handler will find `T` deserialized from the query
+    /// string in `State`.
+    pub fn with_query_string_extractor<T>(self) -> RouterBuilderTo<'a, M, C, P, PE, T>
+    where
+        T: QueryStringExtractor + Send + Sync + 'static,
+    {
+        RouterBuilderTo {
+            matcher: self.matcher,
+            node_builder: self.node_builder,
+            pipeline_chain: self.pipeline_chain,
+            pipelines: self.pipelines,
+            delegation: self.delegation,
+            phantom: PhantomData,
+        }
+    }
+
     pub fn to<NH>(self, new_handler: NH)
     where
         NH: NewHandler + Send + Sync + 'static,
@@ -157,33 +394,136 @@ where
         );
         self.node_builder.add_route(Box::new(route));
     }
+
+    /// Hands off everything below this path to `router`, a `Router` built independently
+    /// (e.g. via a separate call to `build_router`). Unlike `to`, the matched node no
+    /// longer terminates the request here: the remaining path is forwarded to `router` for
+    /// further dispatch, the same way `delegate` is used at the call site.
+    pub fn to_router(self, router: Router) {
+        let dispatcher = DispatcherImpl::new(router, self.pipeline_chain, self.pipelines);
+        let route: RouteImpl<M, PE, QSE> = RouteImpl::new(
+            self.matcher,
+            Box::new(dispatcher),
+            Extractors::new(),
+            Delegation::External,
+        );
+        self.node_builder.add_route(Box::new(route));
+    }
+}
+
+/// A path node resolved by `RouterBuilder::route`, shared across several method/handler
+/// registrations so the tree is only walked once for all of them.
+pub struct RouteBuilder<'a, C, P> {
+    node_builder: &'a mut NodeBuilder,
+    pipeline_chain: C,
+    pipelines: PipelineSet<P>,
+}
+
+impl<'a, C, P> RouteBuilder<'a, C, P>
+where
+    C: PipelineHandleChain<P> + Copy + Send + Sync + 'static,
+    P: Send + Sync + 'static,
+{
+    pub fn get<'b>(&'b mut self) -> DefaultRouterBuilderTo<'b, C, P> {
+        self.request(vec![Method::Get, Method::Head])
+    }
+
+    pub fn post<'b>(&'b mut self) -> DefaultRouterBuilderTo<'b, C, P> {
+        self.request(vec![Method::Post])
+    }
+
+    pub fn put<'b>(&'b mut self) -> DefaultRouterBuilderTo<'b, C, P> {
+        self.request(vec![Method::Put])
+    }
+
+    pub fn patch<'b>(&'b mut self) -> DefaultRouterBuilderTo<'b, C, P> {
+        self.request(vec![Method::Patch])
+    }
+
+    pub fn delete<'b>(&'b mut self) -> DefaultRouterBuilderTo<'b, C, P> {
+        self.request(vec![Method::Delete])
+    }
+
+    pub fn options<'b>(&'b mut self) -> DefaultRouterBuilderTo<'b, C, P> {
+        self.request(vec![Method::Options])
+    }
+
+    pub fn head<'b>(&'b mut self) -> DefaultRouterBuilderTo<'b, C, P> {
+        self.request(vec![Method::Head])
+    }
+
+    pub fn any<'b>(&'b mut self) -> DefaultRouterBuilderTo<'b, C, P> {
+        self.request(all_methods())
+    }
+
+    pub fn request<'b>(&'b mut self, methods: Vec<Method>) -> DefaultRouterBuilderTo<'b, C, P> {
+        let matcher = MethodOnlyRouteMatcher::new(methods);
+
+        RouterBuilderTo {
+            matcher,
+            node_builder: &mut self.node_builder,
+            pipeline_chain: self.pipeline_chain,
+            pipelines: self.pipelines.clone(),
+            delegation: Delegation::Internal,
+            phantom: PhantomData,
+        }
+    }
 }
 
-fn build_subtree<'n, 's, I>(node: &'n mut NodeBuilder, mut i: I) -> &'n mut NodeBuilder
+fn all_methods() -> Vec<Method> {
+    vec![
+        Method::Get,
+        Method::Head,
+        Method::Post,
+        Method::Put,
+        Method::Patch,
+        Method::Delete,
+        Method::Options,
+    ]
+}
+
+fn build_subtree<'n, 's, I>(
+    node: &'n mut NodeBuilder,
+    mut i: I,
+    constraints: &ConstraintSet,
+) -> &'n mut NodeBuilder
 where
     I: Iterator<Item = &'s str>,
 {
     match i.next() {
         Some(segment) => {
-            println!("router::builder::build_subtree descending into {}", segment);
-            let (segment, segment_type) = if segment.starts_with(":") {
-                (&segment[1..], SegmentType::Dynamic)
+            let (segment, segment_type, constraint) = if segment.starts_with(":") {
+                // `:id:[0-9]+` captures `id`, constrained to components matching `[0-9]+`; a
+                // bare `:id` carries no constraint and matches anything, as before. The token
+                // after the second `:` is first looked up as a name registered via
+                // `RouterBuilder::constraint` (e.g. `:id:uint`), falling back to compiling it
+                // as an inline regex when no such name is registered.
+                let mut parts = segment[1..].splitn(2, ':');
+                let name = parts.next().unwrap_or("");
+                let constraint = parts.next().map(|token| {
+                    constraints
+                        .get(token)
+                        .cloned()
+                        .unwrap_or_else(|| Regex::new(&format!("^(?:{})$", token)).unwrap())
+                });
+
+                (name, SegmentType::Dynamic, constraint)
             } else {
-                (segment, SegmentType::Static)
+                (segment, SegmentType::Static, None)
             };
 
-            if !node.has_child(segment, segment_type.clone()) {
-                let node_builder = NodeBuilder::new(segment, segment_type.clone());
+            if !node.has_child_with_constraint(segment, segment_type.clone(), constraint.as_ref()) {
+                let node_builder =
+                    NodeBuilder::with_constraint(segment, segment_type.clone(), constraint.clone());
                 node.add_child(node_builder);
             }
 
-            let child = node.borrow_mut_child(segment, segment_type).unwrap();
-            build_subtree(child, i)
-        }
-        None => {
-            println!("router::builder::build_subtree reached node");
-            node
+            let child = node
+                .borrow_mut_child_with_constraint(segment, segment_type, constraint.as_ref())
+                .unwrap();
+            build_subtree(child, i, constraints)
         }
+        None => node,
     }
 }
 
@@ -200,6 +540,8 @@ mod tests {
     use state::State;
     use handler::{Handler, NewHandlerService};
     use router::route::dispatch::{new_pipeline_set, finalize_pipeline_set};
+    use router::route::matcher::{HeaderRouteMatcher, AcceptHeaderRouteMatcher};
+    use router::tree::SegmentMapping;
 
     mod welcome {
         use super::*;
@@ -215,15 +557,23 @@ mod tests {
         }
     }
 
-    #[test]
-    fn build_router_test() {
-        let pipelines = new_pipeline_set();
-        let (pipelines, default) =
-            pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
+    /// Builds the single-pipeline `(PipelineSet, pipeline chain)` pair every test below
+    /// needs before it can call `build_router`.
+    macro_rules! default_pipeline_chain {
+        () => {{
+            let pipelines = new_pipeline_set();
+            let (pipelines, default) =
+                pipelines.add(new_pipeline().add(NewSessionMiddleware::default()).build());
 
-        let pipelines = finalize_pipeline_set(pipelines);
+            let pipelines = finalize_pipeline_set(pipelines);
 
-        let default_pipeline_chain = (default, ());
+            (pipelines, (default, ()))
+        }};
+    }
+
+    #[test]
+    fn build_router_test() {
+        let (pipelines, default_pipeline_chain) = default_pipeline_chain!();
 
         let router = build_router(default_pipeline_chain, pipelines, |route| {
             route.get("/").to(|| Ok(welcome::index));
@@ -250,4 +600,307 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::Accepted);
     }
+
+    #[test]
+    fn build_router_with_scope_and_delegation_test() {
+        let (pipelines, default_pipeline_chain) = default_pipeline_chain!();
+
+        let sub_router = build_router(default_pipeline_chain, pipelines.clone(), |route| {
+            route.post("/submit").to(|| Ok(api::submit));
+        });
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route.scope("/api", |route| {
+                route.get("/").to(|| Ok(welcome::index));
+            });
+            route.delegate("/api").to_router(sub_router);
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let service = new_service.new_service().unwrap();
+
+        let response = service
+            .call(Request::new(Method::Get, "/api".parse().unwrap()))
+            .wait()
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::Ok);
+
+        let service = new_service.new_service().unwrap();
+
+        let response = service
+            .call(Request::new(Method::Post, "/api/submit".parse().unwrap()))
+            .wait()
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::Accepted);
+    }
+
+    #[test]
+    fn build_router_with_additional_matcher_test() {
+        let (pipelines, default_pipeline_chain) = default_pipeline_chain!();
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route
+                .post("/api/submit")
+                .add_matcher(HeaderRouteMatcher::new(
+                    "content-type",
+                    Some("application/json"),
+                ))
+                .to(|| Ok(api::submit));
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        // A real `Content-Type` header nearly always carries parameters like `charset`;
+        // the matcher must still match on the bare media type.
+        let mut request = Request::new(Method::Post, "/api/submit".parse().unwrap());
+        request.headers_mut().set_raw(
+            "content-type",
+            vec![b"application/json; charset=utf-8".to_vec()],
+        );
+
+        let service = new_service.new_service().unwrap();
+        let response = service.call(request).wait().unwrap();
+
+        assert_eq!(response.status(), StatusCode::Accepted);
+    }
+
+    #[test]
+    fn build_router_with_accept_header_wildcard_test() {
+        let (pipelines, default_pipeline_chain) = default_pipeline_chain!();
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route
+                .get("/thing")
+                .add_matcher(AcceptHeaderRouteMatcher::new("application/json"))
+                .to(|| Ok(api::submit));
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        // curl's default `Accept: */*` should satisfy a matcher for any specific type.
+        let mut request = Request::new(Method::Get, "/thing".parse().unwrap());
+        request.headers_mut().set_raw("accept", vec![b"*/*".to_vec()]);
+
+        let service = new_service.new_service().unwrap();
+        let response = service.call(request).wait().unwrap();
+
+        assert_eq!(response.status(), StatusCode::Accepted);
+    }
+
+    #[test]
+    fn build_router_with_competing_matchers_test() {
+        let (pipelines, default_pipeline_chain) = default_pipeline_chain!();
+
+        fn as_json(state: State, req: Request) -> (State, Response) {
+            (state, Response::new().with_status(StatusCode::Accepted))
+        }
+
+        fn as_html(state: State, req: Request) -> (State, Response) {
+            (state, Response::new().with_status(StatusCode::Ok))
+        }
+
+        // Two routes registered at the same path, distinguished only by `Accept` — the
+        // content-negotiation use case `add_matcher` exists for. Both have to stay live:
+        // registering the second must not shadow the first.
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route
+                .get("/thing")
+                .add_matcher(AcceptHeaderRouteMatcher::new("application/json"))
+                .to(|| Ok(as_json));
+
+            route
+                .get("/thing")
+                .add_matcher(AcceptHeaderRouteMatcher::new("text/html"))
+                .to(|| Ok(as_html));
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let mut json_request = Request::new(Method::Get, "/thing".parse().unwrap());
+        json_request
+            .headers_mut()
+            .set_raw("accept", vec![b"application/json".to_vec()]);
+
+        let service = new_service.new_service().unwrap();
+        let response = service.call(json_request).wait().unwrap();
+
+        assert_eq!(response.status(), StatusCode::Accepted);
+
+        let mut html_request = Request::new(Method::Get, "/thing".parse().unwrap());
+        html_request
+            .headers_mut()
+            .set_raw("accept", vec![b"text/html".to_vec()]);
+
+        let service = new_service.new_service().unwrap();
+        let response = service.call(html_request).wait().unwrap();
+
+        assert_eq!(response.status(), StatusCode::Ok);
+    }
+
+    #[test]
+    fn build_router_with_constrained_segments_test() {
+        let (pipelines, default_pipeline_chain) = default_pipeline_chain!();
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route.get("/users/:id:[0-9]+").to(|| Ok(welcome::index));
+            route.get("/users/:name:[a-z]+").to(|| Ok(api::submit));
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let service = new_service.new_service().unwrap();
+        let response = service
+            .call(Request::new(Method::Get, "/users/42".parse().unwrap()))
+            .wait()
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::Ok);
+
+        let service = new_service.new_service().unwrap();
+        let response = service
+            .call(Request::new(Method::Get, "/users/bob".parse().unwrap()))
+            .wait()
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::Accepted);
+    }
+
+    /// Marker type bound via `with_path_extractor` in
+    /// `build_router_with_typed_extractors_test`, standing in for whatever typed struct an
+    /// app would derive its real path-segment fields onto.
+    struct UserIdPathExtractor;
+
+    impl PathExtractor for UserIdPathExtractor {}
+
+    /// Marker type bound via `with_query_string_extractor` in the same test.
+    struct SearchQueryStringExtractor;
+
+    impl QueryStringExtractor for SearchQueryStringExtractor {}
+
+    #[test]
+    fn build_router_with_typed_extractors_test() {
+        let (pipelines, default_pipeline_chain) = default_pipeline_chain!();
+
+        // The handler itself reads back the `SegmentMapping` that `Router` puts into `State`
+        // for a bound `PathExtractor` to read, rather than just asserting the route still
+        // matches — proving the `:id` segment captured by the tree actually reaches `State`,
+        // not only that the types involved compile.
+        fn show_user(state: State, req: Request) -> (State, Response) {
+            let status = match state.borrow::<SegmentMapping>() {
+                Some(segments) if segments.get("id").map(String::as_str) == Some("42") => {
+                    StatusCode::Ok
+                }
+                _ => StatusCode::InternalServerError,
+            };
+
+            (state, Response::new().with_status(status))
+        }
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route
+                .get("/users/:id:[0-9]+")
+                .with_path_extractor::<UserIdPathExtractor>()
+                .with_query_string_extractor::<SearchQueryStringExtractor>()
+                .to(|| Ok(show_user));
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let service = new_service.new_service().unwrap();
+        let response = service
+            .call(Request::new(
+                Method::Get,
+                "/users/42?q=hello".parse().unwrap(),
+            ))
+            .wait()
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::Ok);
+    }
+
+    #[test]
+    fn build_router_with_named_constraint_test() {
+        let (pipelines, default_pipeline_chain) = default_pipeline_chain!();
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route.constraint("uint", "[0-9]+");
+            route.get("/users/:id:uint").to(|| Ok(welcome::index));
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let service = new_service.new_service().unwrap();
+        let response = service
+            .call(Request::new(Method::Get, "/users/42".parse().unwrap()))
+            .wait()
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::Ok);
+
+        let service = new_service.new_service().unwrap();
+        let response = service
+            .call(Request::new(Method::Get, "/users/bob".parse().unwrap()))
+            .wait()
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NotFound);
+    }
+
+    #[test]
+    fn build_router_with_fallback_test() {
+        let (pipelines, default_pipeline_chain) = default_pipeline_chain!();
+
+        // A status no ordinary unmatched route would ever produce on its own, so this test
+        // can only pass if the fallback handler itself was actually invoked.
+        fn not_found(state: State, req: Request) -> (State, Response) {
+            (state, Response::new().with_status(StatusCode::ImATeapot))
+        }
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            route.get("/").to(|| Ok(welcome::index));
+            route.fallback(|| Ok(not_found));
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let service = new_service.new_service().unwrap();
+        let response = service
+            .call(Request::new(Method::Get, "/missing".parse().unwrap()))
+            .wait()
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::ImATeapot);
+    }
+
+    #[test]
+    fn build_router_with_chained_route_test() {
+        let (pipelines, default_pipeline_chain) = default_pipeline_chain!();
+
+        let router = build_router(default_pipeline_chain, pipelines, |route| {
+            let mut widget = route.route("/widget");
+            widget.get().to(|| Ok(welcome::index));
+            widget.post().to(|| Ok(api::submit));
+        });
+
+        let new_service = NewHandlerService::new(router);
+
+        let service = new_service.new_service().unwrap();
+        let response = service
+            .call(Request::new(Method::Get, "/widget".parse().unwrap()))
+            .wait()
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::Ok);
+
+        let service = new_service.new_service().unwrap();
+        let response = service
+            .call(Request::new(Method::Post, "/widget".parse().unwrap()))
+            .wait()
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::Accepted);
+    }
 }
\ No newline at end of file
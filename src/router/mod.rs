@@ -0,0 +1,137 @@
+use std::io;
+use std::sync::Arc;
+
+use futures::future;
+use hyper::{Request, Response, StatusCode};
+
+use handler::{Handler, HandlerFuture, NewHandler};
+use router::response::finalizer::ResponseFinalizer;
+use router::route::{Delegation, Route};
+use router::tree::{SegmentMapping, Tree};
+use state::State;
+
+pub mod builder;
+pub mod request;
+pub mod response;
+pub mod route;
+pub mod tree;
+
+/// Routes an incoming request to whichever handler was registered for its path, by walking
+/// the `Tree` built by `build_router`. When nothing in the tree matches, consults the
+/// nearest fallback registered along that path via `RouterBuilder::fallback`, and only
+/// falls through to a bare 404 when no such fallback exists either.
+#[derive(Clone)]
+pub struct Router {
+    tree: Arc<Tree>,
+    response_finalizer: Arc<ResponseFinalizer>,
+}
+
+impl Router {
+    pub fn new(tree: Tree, response_finalizer: ResponseFinalizer) -> Router {
+        Router {
+            tree: Arc::new(tree),
+            response_finalizer: Arc::new(response_finalizer),
+        }
+    }
+
+    fn route(&self, state: State, req: Request) -> Box<HandlerFuture> {
+        let path = req.path().trim_left_matches('/').to_owned();
+        let segments: Vec<&str> = if path.is_empty() {
+            Vec::new()
+        } else {
+            path.split('/').collect()
+        };
+
+        match self.tree.find(&segments) {
+            Some((node, segment_mapping, remainder)) => {
+                let route = node.routes().iter().find(|route| route.is_match(&state, &req).is_ok());
+
+                match route {
+                    Some(route) => self.dispatch(&**route, segment_mapping, remainder, state, req),
+                    // Something matched this far in the tree, but no route registered here
+                    // accepts the request as it stands (e.g. right path, wrong method) —
+                    // that's still "not found" from the caller's point of view.
+                    None => self.fallback(&segments, state, req),
+                }
+            }
+            None => self.fallback(&segments, state, req),
+        }
+    }
+
+    /// Hands `state`/`req` off to the matched `route`. Puts `segment_mapping` into `State`
+    /// first so a `PathExtractor` bound via
+    /// `with_path_extractor` can read the values captured for any `Dynamic` segments back
+    /// out. An externally-delegated route additionally gets `req` rebased onto `remainder`
+    /// — the portion of the path not consumed reaching this node — so the sub-router it
+    /// hands off to matches against paths relative to the mount point, the same as if it
+    /// had been built standalone.
+    fn dispatch(
+        &self,
+        route: &(Route + Send + Sync),
+        segment_mapping: SegmentMapping,
+        remainder: &[&str],
+        mut state: State,
+        req: Request,
+    ) -> Box<HandlerFuture> {
+        state.put(segment_mapping);
+
+        match route.delegation() {
+            Delegation::Internal => route.dispatch(state, req),
+            Delegation::External => route.dispatch(state, rebase_request(req, remainder)),
+        }
+    }
+
+    fn fallback(&self, segments: &[&str], state: State, req: Request) -> Box<HandlerFuture> {
+        match self.tree.nearest_fallback(segments) {
+            Some(fallback) => match fallback.new_handler() {
+                Ok(handler) => handler.handle(state, req),
+                Err(_) => Box::new(future::ok((
+                    state,
+                    Response::new().with_status(StatusCode::InternalServerError),
+                ))),
+            },
+            None => Box::new(future::ok((state, Response::new().with_status(StatusCode::NotFound)))),
+        }
+    }
+}
+
+/// Rewrites `req`'s path to `remainder`, preserving everything else about the request, so a
+/// sub-router handed off to via `delegate(..).to_router(..)` sees the same relative path it
+/// would if it had received the request directly rather than via its parent's mount point.
+fn rebase_request(req: Request, remainder: &[&str]) -> Request {
+    let path = if remainder.is_empty() {
+        "/".to_owned()
+    } else {
+        format!("/{}", remainder.join("/"))
+    };
+
+    let uri = match req.query() {
+        Some(query) => format!("{}?{}", path, query),
+        None => path,
+    };
+
+    let method = req.method().clone();
+    let version = req.version();
+    let headers = req.headers().clone();
+    let body = req.body();
+
+    let mut rebased = Request::new(method, uri.parse().unwrap());
+    rebased.set_version(version);
+    *rebased.headers_mut() = headers;
+    rebased.set_body(body);
+    rebased
+}
+
+impl NewHandler for Router {
+    type Instance = Router;
+
+    fn new_handler(&self) -> io::Result<Router> {
+        Ok(self.clone())
+    }
+}
+
+impl Handler for Router {
+    fn handle(self, state: State, req: Request) -> Box<HandlerFuture> {
+        self.route(state, req)
+    }
+}
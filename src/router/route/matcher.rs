@@ -0,0 +1,212 @@
+use hyper::{Method, Request};
+use hyper::header::Accept;
+
+use router::non_match::RouteNonMatch;
+use state::State;
+
+/// Decides whether a route applies to a request, beyond the path already having matched in
+/// the tree. `RouterBuilderTo::add_matcher` composes additional `RouteMatcher`s
+/// onto a route's `MethodOnlyRouteMatcher` with AND semantics via `AndRouteMatcher`.
+pub trait RouteMatcher {
+    fn is_match(&self, state: &State, req: &Request) -> Result<(), RouteNonMatch>;
+}
+
+/// Matches a request whose method is one of `methods`. This is the matcher `request`/`get`/
+/// `post`/etc. build on `RouterBuilder`.
+pub struct MethodOnlyRouteMatcher {
+    methods: Vec<Method>,
+}
+
+impl MethodOnlyRouteMatcher {
+    pub fn new(methods: Vec<Method>) -> MethodOnlyRouteMatcher {
+        MethodOnlyRouteMatcher { methods }
+    }
+}
+
+impl RouteMatcher for MethodOnlyRouteMatcher {
+    fn is_match(&self, _state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        if self.methods.iter().any(|method| method == req.method()) {
+            Ok(())
+        } else {
+            Err(RouteNonMatch::new(hyper::StatusCode::MethodNotAllowed))
+        }
+    }
+}
+
+/// Matches any request regardless of method. Used by `RouterBuilder::delegate`, where the
+/// sub-router being handed off to is responsible for its own method dispatch, not the parent
+/// tree.
+pub struct AnyRouteMatcher;
+
+impl AnyRouteMatcher {
+    pub fn new() -> AnyRouteMatcher {
+        AnyRouteMatcher
+    }
+}
+
+impl RouteMatcher for AnyRouteMatcher {
+    fn is_match(&self, _state: &State, _req: &Request) -> Result<(), RouteNonMatch> {
+        Ok(())
+    }
+}
+
+/// Matches a request which carries a given header, optionally requiring a specific value.
+///
+/// ```rust
+/// route
+///     .get("/thing")
+///     .add_matcher(HeaderRouteMatcher::new("content-type", Some("application/json")))
+///     .to(handler);
+/// ```
+pub struct HeaderRouteMatcher {
+    name: &'static str,
+    value: Option<&'static str>,
+}
+
+impl HeaderRouteMatcher {
+    pub fn new(name: &'static str, value: Option<&'static str>) -> HeaderRouteMatcher {
+        HeaderRouteMatcher { name, value }
+    }
+}
+
+impl RouteMatcher for HeaderRouteMatcher {
+    fn is_match(&self, _state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        match req.headers().get_raw(self.name) {
+            Some(raw) => match self.value {
+                Some(expected) => {
+                    let matches = raw.iter().any(|line| {
+                        ::std::str::from_utf8(line)
+                            .map(|value| media_type(value) == media_type(expected))
+                            .unwrap_or(false)
+                    });
+                    if matches {
+                        Ok(())
+                    } else {
+                        Err(RouteNonMatch::new(hyper::StatusCode::NotFound))
+                    }
+                }
+                None => Ok(()),
+            },
+            None => Err(RouteNonMatch::new(hyper::StatusCode::NotFound)),
+        }
+    }
+}
+
+/// Strips any `;`-separated parameters (e.g. `charset=utf-8`) off a header value, leaving
+/// just the bare value to compare against — so `Content-Type: application/json;
+/// charset=utf-8` still satisfies a matcher built for `"application/json"`.
+fn media_type(raw: &str) -> &str {
+    raw.split(';').next().unwrap_or("").trim()
+}
+
+/// Splits a bare media type (parameters already stripped) into its type and subtype, e.g.
+/// `"application/json"` into `("application", "json")`.
+fn media_type_parts(raw: &str) -> (&str, &str) {
+    let mut parts = media_type(raw).splitn(2, '/');
+    (parts.next().unwrap_or(""), parts.next().unwrap_or(""))
+}
+
+/// Matches a request whose query string carries a given parameter, optionally requiring a
+/// specific value.
+pub struct QueryParamRouteMatcher {
+    name: &'static str,
+    value: Option<&'static str>,
+}
+
+impl QueryParamRouteMatcher {
+    pub fn new(name: &'static str, value: Option<&'static str>) -> QueryParamRouteMatcher {
+        QueryParamRouteMatcher { name, value }
+    }
+}
+
+impl RouteMatcher for QueryParamRouteMatcher {
+    fn is_match(&self, _state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        let query = req.query().unwrap_or("");
+
+        let mut found = None;
+        for pair in query.split('&') {
+            let mut parts = pair.splitn(2, '=');
+            if let (Some(key), value) = (parts.next(), parts.next()) {
+                if key == self.name {
+                    found = Some(value.unwrap_or(""));
+                    break;
+                }
+            }
+        }
+
+        match (found, self.value) {
+            (Some(_), None) => Ok(()),
+            (Some(actual), Some(expected)) if actual == expected => Ok(()),
+            _ => Err(RouteNonMatch::new(hyper::StatusCode::NotFound)),
+        }
+    }
+}
+
+/// Matches a request whose `Accept` header includes a given media type, enabling
+/// content-negotiation-style routing (e.g. `GET /thing` dispatching differently for
+/// `Accept: application/json` vs `Accept: text/html`).
+pub struct AcceptHeaderRouteMatcher {
+    media_type: &'static str,
+}
+
+impl AcceptHeaderRouteMatcher {
+    pub fn new(media_type: &'static str) -> AcceptHeaderRouteMatcher {
+        AcceptHeaderRouteMatcher { media_type }
+    }
+}
+
+impl RouteMatcher for AcceptHeaderRouteMatcher {
+    fn is_match(&self, _state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        match req.headers().get::<Accept>() {
+            Some(accept) => {
+                let (ty, subty) = media_type_parts(self.media_type);
+
+                let matches = accept.iter().any(|quality_item| {
+                    let item = quality_item.item.to_string();
+                    let (item_ty, item_subty) = media_type_parts(&item);
+                    (item_ty == "*" || item_ty == ty) && (item_subty == "*" || item_subty == subty)
+                });
+
+                if matches {
+                    Ok(())
+                } else {
+                    Err(RouteNonMatch::new(hyper::StatusCode::NotAcceptable))
+                }
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Combines two `RouteMatcher`s with AND semantics: the composed matcher only matches when
+/// both of its parts do. Built by `RouterBuilderTo::add_matcher`, which chains
+/// additional matchers onto a route alongside its `MethodOnlyRouteMatcher`.
+pub struct AndRouteMatcher<T, U>
+where
+    T: RouteMatcher,
+    U: RouteMatcher,
+{
+    t: T,
+    u: U,
+}
+
+impl<T, U> AndRouteMatcher<T, U>
+where
+    T: RouteMatcher,
+    U: RouteMatcher,
+{
+    pub fn new(t: T, u: U) -> AndRouteMatcher<T, U> {
+        AndRouteMatcher { t, u }
+    }
+}
+
+impl<T, U> RouteMatcher for AndRouteMatcher<T, U>
+where
+    T: RouteMatcher,
+    U: RouteMatcher,
+{
+    fn is_match(&self, state: &State, req: &Request) -> Result<(), RouteNonMatch> {
+        self.t.is_match(state, req)?;
+        self.u.is_match(state, req)
+    }
+}
@@ -0,0 +1,104 @@
+pub mod node;
+
+use std::collections::HashMap;
+
+use handler::NewHandler;
+use router::tree::node::{Node, NodeBuilder, SegmentType};
+
+/// The path-segment values captured by `Dynamic` nodes along a matched route, keyed by
+/// segment name (e.g. `:id` in `/users/:id` captures `"id" => "42"` for `/users/42`). Put
+/// into `State` by `Router` ahead of dispatch so a route's `PathExtractor` can read the
+/// values it needs back out.
+pub type SegmentMapping = HashMap<String, String>;
+
+/// Builds the route tree behind a `Router`, one `NodeBuilder` at a time, as `RouterBuilder`
+/// walks each registered path. `finalize` converts it into the immutable `Tree` consulted on
+/// every request.
+pub struct TreeBuilder {
+    root: NodeBuilder,
+}
+
+impl TreeBuilder {
+    pub fn new() -> TreeBuilder {
+        TreeBuilder { root: NodeBuilder::new("", SegmentType::Static) }
+    }
+
+    pub fn borrow_root_mut(&mut self) -> &mut NodeBuilder {
+        &mut self.root
+    }
+
+    pub fn finalize(self) -> Tree {
+        Tree { root: self.root.finalize() }
+    }
+}
+
+/// The immutable route tree a `Router` matches requests against.
+pub struct Tree {
+    root: Node,
+}
+
+impl Tree {
+    pub fn root(&self) -> &Node {
+        &self.root
+    }
+
+    /// Finds the node matching as much of `segments` as possible, together with the
+    /// segment values captured along the way and whatever suffix of `segments` was left
+    /// unconsumed.
+    ///
+    /// The remainder is empty unless descent stops early at a node carrying an
+    /// externally-delegated route (see `Node::has_external_delegation`) — in which case the
+    /// unconsumed segments are handed back so `Router` can forward them to the sub-router,
+    /// rather than requiring the whole path to already exist in this tree. Any other
+    /// partial match yields `None`.
+    pub fn find<'n, 's>(
+        &'n self,
+        segments: &'s [&'s str],
+    ) -> Option<(&'n Node, SegmentMapping, &'s [&'s str])> {
+        let mut node = &self.root;
+        let mut segment_mapping = SegmentMapping::new();
+
+        for (consumed, segment) in segments.iter().enumerate() {
+            match node.children().iter().find(|child| child.matches_segment(segment)) {
+                Some(child) => {
+                    if *child.segment_type() == SegmentType::Dynamic {
+                        segment_mapping.insert(child.segment().to_owned(), (*segment).to_owned());
+                    }
+                    node = child;
+                }
+                None => {
+                    return if node.has_external_delegation() {
+                        Some((node, segment_mapping, &segments[consumed..]))
+                    } else {
+                        None
+                    };
+                }
+            }
+        }
+
+        Some((node, segment_mapping, &segments[segments.len()..]))
+    }
+
+    /// Finds the nearest registered fallback handler along the path to `segments`: the
+    /// deepest node on that path which has one (registered via `RouterBuilder::fallback`),
+    /// falling back further up the tree when a node along the way has none of its own, or
+    /// `None` if nothing from the root down was ever given a fallback.
+    pub fn nearest_fallback(&self, segments: &[&str]) -> Option<&(NewHandler + Send + Sync)> {
+        let mut node = &self.root;
+        let mut fallback = node.fallback();
+
+        for segment in segments {
+            match node.children().iter().find(|child| child.matches_segment(segment)) {
+                Some(child) => {
+                    node = child;
+                    if node.fallback().is_some() {
+                        fallback = node.fallback();
+                    }
+                }
+                None => break,
+            }
+        }
+
+        fallback
+    }
+}
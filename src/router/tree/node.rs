@@ -0,0 +1,181 @@
+use regex::Regex;
+
+use handler::NewHandler;
+use router::route::{Delegation, Route};
+
+/// The type of a single path segment in the route tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SegmentType {
+    Static,
+    Dynamic,
+}
+
+/// A single node in the route tree under construction.
+///
+/// A node's children are keyed by segment name and `SegmentType`, plus, for `Dynamic`
+/// segments, an optional constraint. This lets two dynamic children with different
+/// constraints (e.g. `:id:[0-9]+` and `:name:[a-z]+`) coexist under the same parent and be
+/// disambiguated at match time, while an unconstrained `:id` still matches anything.
+pub struct NodeBuilder {
+    segment: String,
+    segment_type: SegmentType,
+    constraint: Option<Regex>,
+    children: Vec<NodeBuilder>,
+    routes: Vec<Box<Route + Send + Sync>>,
+    fallback: Option<Box<NewHandler + Send + Sync>>,
+}
+
+impl NodeBuilder {
+    pub fn new(segment: &str, segment_type: SegmentType) -> NodeBuilder {
+        NodeBuilder::with_constraint(segment, segment_type, None)
+    }
+
+    /// Builds a node for a segment constrained by `constraint`, a regex which the path
+    /// component must satisfy, anchored to the full segment, to be accepted. `constraint`
+    /// is only meaningful for `Dynamic` segments.
+    pub fn with_constraint(
+        segment: &str,
+        segment_type: SegmentType,
+        constraint: Option<Regex>,
+    ) -> NodeBuilder {
+        NodeBuilder {
+            segment: segment.to_owned(),
+            segment_type,
+            constraint,
+            children: Vec::new(),
+            routes: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    pub fn has_child(&self, segment: &str, segment_type: SegmentType) -> bool {
+        self.has_child_with_constraint(segment, segment_type, None)
+    }
+
+    pub fn has_child_with_constraint(
+        &self,
+        segment: &str,
+        segment_type: SegmentType,
+        constraint: Option<&Regex>,
+    ) -> bool {
+        self.find_child(segment, segment_type, constraint).is_some()
+    }
+
+    pub fn add_child(&mut self, node_builder: NodeBuilder) {
+        self.children.push(node_builder);
+    }
+
+    pub fn borrow_mut_child(
+        &mut self,
+        segment: &str,
+        segment_type: SegmentType,
+    ) -> Option<&mut NodeBuilder> {
+        self.borrow_mut_child_with_constraint(segment, segment_type, None)
+    }
+
+    pub fn borrow_mut_child_with_constraint(
+        &mut self,
+        segment: &str,
+        segment_type: SegmentType,
+        constraint: Option<&Regex>,
+    ) -> Option<&mut NodeBuilder> {
+        let constraint = constraint.map(Regex::as_str);
+        self.children.iter_mut().find(|child| {
+            child.segment == segment && child.segment_type == segment_type
+                && child.constraint.as_ref().map(Regex::as_str) == constraint
+        })
+    }
+
+    fn find_child(
+        &self,
+        segment: &str,
+        segment_type: SegmentType,
+        constraint: Option<&Regex>,
+    ) -> Option<&NodeBuilder> {
+        let constraint = constraint.map(Regex::as_str);
+        self.children.iter().find(|child| {
+            child.segment == segment && child.segment_type == segment_type
+                && child.constraint.as_ref().map(Regex::as_str) == constraint
+        })
+    }
+
+    pub fn add_route(&mut self, route: Box<Route + Send + Sync>) {
+        self.routes.push(route);
+    }
+
+    /// Registers the handler invoked when no route at or below this node matches a
+    /// request. A node without its own fallback defers to the nearest ancestor that has
+    /// one, so a scope's fallback only covers unmatched requests within that scope.
+    pub fn set_fallback(&mut self, new_handler: Box<NewHandler + Send + Sync>) {
+        self.fallback = Some(new_handler);
+    }
+
+    /// Converts this node, and all of its children, into the immutable `Node` a `Tree`
+    /// matches requests against. Carries `routes` and `fallback` across unchanged, so a
+    /// fallback registered on a `NodeBuilder` remains reachable after the tree is built.
+    pub fn finalize(self) -> Node {
+        Node {
+            segment: self.segment,
+            segment_type: self.segment_type,
+            constraint: self.constraint,
+            children: self.children.into_iter().map(NodeBuilder::finalize).collect(),
+            routes: self.routes,
+            fallback: self.fallback,
+        }
+    }
+}
+
+/// A single node in the finalized, immutable route tree consulted by `Router`.
+pub struct Node {
+    segment: String,
+    segment_type: SegmentType,
+    constraint: Option<Regex>,
+    children: Vec<Node>,
+    routes: Vec<Box<Route + Send + Sync>>,
+    fallback: Option<Box<NewHandler + Send + Sync>>,
+}
+
+impl Node {
+    pub fn segment(&self) -> &str {
+        &self.segment
+    }
+
+    pub fn segment_type(&self) -> &SegmentType {
+        &self.segment_type
+    }
+
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+
+    pub fn routes(&self) -> &[Box<Route + Send + Sync>] {
+        &self.routes
+    }
+
+    /// Whether this node carries a route that delegates to an externally-built `Router`
+    /// (registered via `RouterBuilder::delegate(..).to_router(..)`). When none of such a
+    /// node's children matches the next segment, `Tree::find` stops descending there even
+    /// though segments remain unconsumed, handing the remainder off to the sub-router
+    /// instead of requiring it to already exist in this tree.
+    pub fn has_external_delegation(&self) -> bool {
+        self.routes.iter().any(|route| route.delegation() == Delegation::External)
+    }
+
+    /// The handler registered via `RouterBuilder::fallback` for this node, if any.
+    pub fn fallback(&self) -> Option<&(NewHandler + Send + Sync)> {
+        self.fallback.as_ref().map(|h| &**h)
+    }
+
+    /// Whether `segment` satisfies this node: an exact match for `Static` nodes, or the
+    /// node's constraint (if any) for `Dynamic` ones. An unconstrained `Dynamic` node
+    /// matches any component.
+    pub fn matches_segment(&self, segment: &str) -> bool {
+        match self.segment_type {
+            SegmentType::Static => self.segment == segment,
+            SegmentType::Dynamic => self.constraint
+                .as_ref()
+                .map(|re| re.is_match(segment))
+                .unwrap_or(true),
+        }
+    }
+}